@@ -1,10 +1,21 @@
 //! Crate for safe conversion between units of memory.
+//!
+//! Enable the `serde` feature to derive `Serialize`/`Deserialize` on the unit
+//! types, or the `rkyv` feature to derive `Archive`/`Serialize`/`Deserialize`
+//! for zero-copy (de)serialization. Both are off by default and keep the
+//! crate `no_std`.
 
 #![deny(missing_docs)]
 #![no_std]
 
+#[cfg(test)]
+extern crate std;
+
+use core::convert::TryFrom;
+use core::fmt;
 use core::mem;
 use core::ops;
+use core::str::FromStr;
 
 /// [Memory page][memory page] size in bytes.
 /// 
@@ -18,6 +29,10 @@ pub const PAGE_SIZE: Bytes = Bytes(65536);
 #[cfg(not(target_arch = "wasm32"))]
 pub const PAGE_SIZE: Bytes = Bytes(4096);
 
+/// The page size (in bytes) [`Pages`] uses when no explicit `SIZE` is given.
+///
+/// [`Pages`]: struct.Pages.html
+const DEFAULT_PAGE_SIZE: usize = PAGE_SIZE.0;
 
 /// Returns the size of a type in [`Bytes`].
 /// 
@@ -47,7 +62,8 @@ pub fn size_of<T>() -> Bytes {
 /// ```rust
 /// # use memory_units::*;
 /// // `bytes` contains the size of 1 memory page in bytes.
-/// let mut bytes: Bytes = Pages(1).into();
+/// let one_page: Pages = Pages(1);
+/// let mut bytes: Bytes = one_page.into();
 ///
 /// // Adding 1 to `bytes` makes it larger than the single page.
 /// bytes.0 += 1;
@@ -59,9 +75,12 @@ pub trait RoundUpTo<T> {
     fn round_up_to(self) -> T;
 }
 
+// `$name` is either a plain unit type (`Bytes`) or one generic over a
+// `const SIZE: usize` (`Pages<const SIZE: usize>`), such as
+// [`Pages`](struct.Pages.html).
 macro_rules! impl_unit_ops {
-    ( $name:ident ) => {
-        impl<T: Into<Self>> ops::Add<T> for $name {
+    ( $name:ident $(< const $gen:ident : $gen_ty:ty >)? ) => {
+        impl<$(const $gen: $gen_ty,)? T: Into<Self>> ops::Add<T> for $name$(<$gen>)? {
             type Output = Self;
 
             #[inline]
@@ -70,7 +89,7 @@ macro_rules! impl_unit_ops {
             }
         }
 
-        impl<T: Into<Self>> ops::Sub<T> for $name {
+        impl<$(const $gen: $gen_ty,)? T: Into<Self>> ops::Sub<T> for $name$(<$gen>)? {
             type Output = Self;
 
             #[inline]
@@ -79,7 +98,7 @@ macro_rules! impl_unit_ops {
             }
         }
 
-        impl<T: Into<Self>> ops::Mul<T> for $name {
+        impl<$(const $gen: $gen_ty,)? T: Into<Self>> ops::Mul<T> for $name$(<$gen>)? {
             type Output = Self;
 
             #[inline]
@@ -88,7 +107,7 @@ macro_rules! impl_unit_ops {
             }
         }
 
-        impl<T: Into<Self>> ops::Div<T> for $name {
+        impl<$(const $gen: $gen_ty,)? T: Into<Self>> ops::Div<T> for $name$(<$gen>)? {
             type Output = Self;
 
             #[inline]
@@ -96,25 +115,83 @@ macro_rules! impl_unit_ops {
                 $name(self.0 / rhs.into().0)
             }
         }
+
+        impl$(<const $gen: $gen_ty>)? $name$(<$gen>)? {
+            /// Checked addition. Returns `None` if the result would overflow `usize`.
+            #[inline]
+            pub fn checked_add<T: Into<Self>>(self, rhs: T) -> Option<Self> {
+                self.0.checked_add(rhs.into().0).map($name)
+            }
+
+            /// Checked subtraction. Returns `None` if the result would underflow.
+            #[inline]
+            pub fn checked_sub<T: Into<Self>>(self, rhs: T) -> Option<Self> {
+                self.0.checked_sub(rhs.into().0).map($name)
+            }
+
+            /// Checked multiplication. Returns `None` if the result would overflow `usize`.
+            #[inline]
+            pub fn checked_mul<T: Into<Self>>(self, rhs: T) -> Option<Self> {
+                self.0.checked_mul(rhs.into().0).map($name)
+            }
+
+            /// Saturating addition. Returns `usize::MAX` (wrapped in `Self`) on overflow.
+            #[inline]
+            pub fn saturating_add<T: Into<Self>>(self, rhs: T) -> Self {
+                $name(self.0.saturating_add(rhs.into().0))
+            }
+
+            /// Saturating subtraction. Returns `Self(0)` on underflow.
+            #[inline]
+            pub fn saturating_sub<T: Into<Self>>(self, rhs: T) -> Self {
+                $name(self.0.saturating_sub(rhs.into().0))
+            }
+        }
     }
 }
 
 /// Memory size specified in bytes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct Bytes(pub usize);
 impl_unit_ops!(Bytes);
 
 /// Memory size specified in words.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct Words(pub usize);
 impl_unit_ops!(Words);
 
-/// Memory size specified in [memory page].
-/// 
+/// Memory size specified in [memory page]s of `SIZE` bytes each.
+///
+/// `SIZE` defaults to the platform's native [`PAGE_SIZE`], but can be set to
+/// track other granularities that coexist in the same address space, such as
+/// `x86_64`'s 2 MiB/1 GiB huge pages (see [`HugePages2Mib`] and
+/// [`HugePages1Gib`]). Conversions between two `Pages<SIZE>` of different
+/// `SIZE` go through [`Bytes`].
+///
 /// [memory page]: https://en.wikipedia.org/wiki/Page_(computer_memory)
+/// [`PAGE_SIZE`]: constant.PAGE_SIZE.html
+/// [`HugePages2Mib`]: type.HugePages2Mib.html
+/// [`HugePages1Gib`]: type.HugePages1Gib.html
+/// [`Bytes`]: struct.Bytes.html
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Pages(pub usize);
-impl_unit_ops!(Pages);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct Pages<const SIZE: usize = DEFAULT_PAGE_SIZE>(pub usize);
+impl_unit_ops!(Pages<const SIZE: usize>);
+
+/// [`Pages`] of 2 MiB each, as used by `x86_64` huge pages.
+///
+/// [`Pages`]: struct.Pages.html
+pub type HugePages2Mib = Pages<{ 2 * 1024 * 1024 }>;
+
+/// [`Pages`] of 1 GiB each, as used by `x86_64` huge pages.
+///
+/// [`Pages`]: struct.Pages.html
+pub type HugePages1Gib = Pages<{ 1024 * 1024 * 1024 }>;
 
 impl From<Words> for Bytes {
     #[inline]
@@ -125,13 +202,13 @@ impl From<Words> for Bytes {
 
 #[inline]
 fn round_up_to(n: usize, divisor: usize) -> usize {
-    (n + divisor - 1) / divisor
+    n.div_ceil(divisor)
 }
 
-impl From<Pages> for Bytes {
+impl<const SIZE: usize> From<Pages<SIZE>> for Bytes {
     #[inline]
-    fn from(pages: Pages) -> Bytes {
-        Bytes(pages.0 * PAGE_SIZE.0)
+    fn from(pages: Pages<SIZE>) -> Bytes {
+        Bytes(pages.0 * SIZE)
     }
 }
 
@@ -142,24 +219,490 @@ impl RoundUpTo<Words> for Bytes {
     }
 }
 
-impl RoundUpTo<Pages> for Bytes {
+impl<const SIZE: usize> RoundUpTo<Pages<SIZE>> for Bytes {
+    #[inline]
+    fn round_up_to(self) -> Pages<SIZE> {
+        Pages(round_up_to(self.0, SIZE))
+    }
+}
+
+impl<const SIZE: usize> From<Pages<SIZE>> for Words {
+    #[inline]
+    fn from(pages: Pages<SIZE>) -> Words {
+        Words(pages.0 * SIZE / mem::size_of::<usize>())
+    }
+}
+
+/// Errors produced by the fallible conversions between memory units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryError {
+    /// The conversion overflowed `usize`.
+    Overflow,
+    /// The resulting number of pages exceeds the configured maximum.
+    TooManyPages {
+        /// The number of pages the conversion produced.
+        pages: usize,
+        /// The maximum number of pages allowed.
+        max_pages: usize,
+    },
+}
+
+/// A trait defining fallible round up conversion between various memory units.
+///
+/// Unlike [`RoundUpTo`], this never panics or silently wraps on overflow; it
+/// returns a [`MemoryError`] instead.
+///
+/// [`RoundUpTo`]: trait.RoundUpTo.html
+/// [`MemoryError`]: enum.MemoryError.html
+pub trait TryRoundUpTo<T>: Sized {
+    /// Returns the minimum number of `T` to fit the space occupied by `self`,
+    /// or `Err` if the conversion would overflow.
+    fn try_round_up_to(self) -> Result<T, MemoryError>;
+}
+
+#[inline]
+fn try_round_up_to(n: usize, divisor: usize) -> Option<usize> {
+    n.checked_add(divisor - 1).map(|n| n / divisor)
+}
+
+impl TryRoundUpTo<Words> for Bytes {
+    #[inline]
+    fn try_round_up_to(self) -> Result<Words, MemoryError> {
+        try_round_up_to(self.0, mem::size_of::<usize>())
+            .map(Words)
+            .ok_or(MemoryError::Overflow)
+    }
+}
+
+impl<const SIZE: usize> TryRoundUpTo<Pages<SIZE>> for Bytes {
+    #[inline]
+    fn try_round_up_to(self) -> Result<Pages<SIZE>, MemoryError> {
+        try_round_up_to(self.0, SIZE)
+            .map(Pages)
+            .ok_or(MemoryError::Overflow)
+    }
+}
+
+impl Bytes {
+    /// Like [`try_round_up_to`], but additionally rejects a page count above `max_pages`.
+    ///
+    /// This mirrors the address-space bound that WebAssembly and similar embedders
+    /// place on the number of memory pages a linear memory may grow to.
+    ///
+    /// [`try_round_up_to`]: trait.TryRoundUpTo.html#tymethod.try_round_up_to
     #[inline]
-    fn round_up_to(self) -> Pages {
-        Pages(round_up_to(self.0, PAGE_SIZE.0))
+    pub fn try_round_up_to_pages<const SIZE: usize>(
+        self,
+        max_pages: usize,
+    ) -> Result<Pages<SIZE>, MemoryError> {
+        let pages: Pages<SIZE> = TryRoundUpTo::try_round_up_to(self)?;
+        if pages.0 > max_pages {
+            Err(MemoryError::TooManyPages {
+                pages: pages.0,
+                max_pages,
+            })
+        } else {
+            Ok(pages)
+        }
     }
 }
 
-impl From<Pages> for Words {
+impl<const SIZE: usize> Pages<SIZE> {
+    /// Fallibly converts this page count into [`Bytes`], returning
+    /// `Err(MemoryError::Overflow)` instead of overflowing `usize`.
+    ///
+    /// This can't be a [`TryFrom`] impl: that trait is already blanket-implemented
+    /// for any pair of types connected by a (necessarily infallible) [`From`], and
+    /// `Bytes` already has one ([`From<Pages<SIZE>>`]).
+    ///
+    /// [`Bytes`]: struct.Bytes.html
+    /// [`TryFrom`]: https://doc.rust-lang.org/core/convert/trait.TryFrom.html
+    /// [`From`]: https://doc.rust-lang.org/core/convert/trait.From.html
+    /// [`From<Pages<SIZE>>`]: struct.Bytes.html
     #[inline]
-    fn from(pages: Pages) -> Words {
-        Words(pages.0 * PAGE_SIZE.0 / mem::size_of::<usize>())
+    pub fn try_to_bytes(self) -> Result<Bytes, MemoryError> {
+        self.0.checked_mul(SIZE).map(Bytes).ok_or(MemoryError::Overflow)
     }
 }
 
-impl RoundUpTo<Pages> for Words {
+impl<const SIZE: usize> RoundUpTo<Pages<SIZE>> for Words {
     #[inline]
-    fn round_up_to(self) -> Pages {
+    fn round_up_to(self) -> Pages<SIZE> {
         let bytes: Bytes = self.into();
         bytes.round_up_to()
     }
 }
+
+
+const BINARY_UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+const DECIMAL_UNITS: [&str; 6] = ["B", "KB", "MB", "GB", "TB", "PB"];
+
+/// A human-readable rendering of a [`Bytes`] value, returned by [`Bytes::to_string_as`].
+///
+/// [`Bytes`]: struct.Bytes.html
+/// [`Bytes::to_string_as`]: struct.Bytes.html#method.to_string_as
+pub struct HumanBytes {
+    bytes: usize,
+    binary: bool,
+}
+
+impl fmt::Display for HumanBytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (base, units) = if self.binary {
+            (1024.0, &BINARY_UNITS)
+        } else {
+            (1000.0, &DECIMAL_UNITS)
+        };
+
+        let mut value = self.bytes as f64;
+        let mut unit = 0;
+        while value >= base && unit < units.len() - 1 {
+            value /= base;
+            unit += 1;
+        }
+
+        if unit == 0 {
+            write!(f, "{} {}", self.bytes, units[unit])
+        } else {
+            write!(f, "{:.1} {}", value, units[unit])
+        }
+    }
+}
+
+impl Bytes {
+    /// Renders this size as a human-readable string, e.g. `"1.5 GiB"` (binary)
+    /// or `"1.5 GB"` (decimal), following [`bytesize`]'s formatting.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use memory_units::*;
+    /// assert_eq!(Bytes(1536).to_string_as(true).to_string(), "1.5 KiB");
+    /// assert_eq!(Bytes(1500).to_string_as(false).to_string(), "1.5 KB");
+    /// assert_eq!(Bytes(512).to_string_as(true).to_string(), "512 B");
+    /// ```
+    ///
+    /// [`bytesize`]: https://docs.rs/bytesize
+    #[inline]
+    pub fn to_string_as(self, binary: bool) -> HumanBytes {
+        HumanBytes {
+            bytes: self.0,
+            binary,
+        }
+    }
+}
+
+/// Renders the size using the binary (`KiB`/`MiB`/...) unit system.
+///
+/// # Example
+///
+/// ```rust
+/// # use memory_units::*;
+/// assert_eq!(Bytes(1536).to_string(), "1.5 KiB");
+/// ```
+impl fmt::Display for Bytes {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.to_string_as(true), f)
+    }
+}
+
+/// An error returned by [`Bytes`]'s [`FromStr`] implementation.
+///
+/// [`Bytes`]: struct.Bytes.html
+/// [`FromStr`]: https://doc.rust-lang.org/core/str/trait.FromStr.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseBytesError {
+    /// The numeric portion of the string was missing or malformed.
+    InvalidNumber,
+    /// The unit suffix was not one of the recognized `B`/`K`/`KiB`/... units.
+    UnknownUnit,
+    /// The parsed value does not fit in a `usize`, or the number has more
+    /// fractional digits than can be scaled by the unit within a `u64`.
+    Overflow,
+}
+
+#[inline]
+fn parse_digits(digits: &str) -> Result<u64, ParseBytesError> {
+    let mut value: u64 = 0;
+    for c in digits.chars() {
+        let digit = c.to_digit(10).ok_or(ParseBytesError::InvalidNumber)?;
+        value = value
+            .checked_mul(10)
+            .and_then(|v| v.checked_add(u64::from(digit)))
+            .ok_or(ParseBytesError::Overflow)?;
+    }
+    Ok(value)
+}
+
+impl FromStr for Bytes {
+    type Err = ParseBytesError;
+
+    /// Parses strings like `"4KiB"` or `"1.5 GB"` into a [`Bytes`] value.
+    ///
+    /// The leading numeric span (digits with an optional single `.`) is parsed
+    /// by hand, since this crate is `no_std`, and the trailing alphabetic
+    /// suffix is matched case-insensitively against `B`, `K`/`KB`/`KIB`,
+    /// `M`/`MB`/`MIB`, `G`/`GB`/`GIB`, `T`/`TB`/`TIB` and `P`/`PB`/`PIB`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use memory_units::*;
+    /// assert_eq!("4KiB".parse(), Ok(Bytes(4 * 1024)));
+    /// assert_eq!("1.5KB".parse(), Ok(Bytes(1500)));
+    /// assert!("4XiB".parse::<Bytes>().is_err());
+    /// ```
+    ///
+    /// [`Bytes`]: struct.Bytes.html
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let split_at = s
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(s.len());
+        let (number, suffix) = s.split_at(split_at);
+        let suffix = suffix.trim();
+
+        if number.is_empty() {
+            return Err(ParseBytesError::InvalidNumber);
+        }
+
+        let multiplier: u64 = if suffix.is_empty() || suffix.eq_ignore_ascii_case("b") {
+            1
+        } else if suffix.eq_ignore_ascii_case("k") || suffix.eq_ignore_ascii_case("kb") {
+            1000
+        } else if suffix.eq_ignore_ascii_case("kib") {
+            1024
+        } else if suffix.eq_ignore_ascii_case("m") || suffix.eq_ignore_ascii_case("mb") {
+            1000 * 1000
+        } else if suffix.eq_ignore_ascii_case("mib") {
+            1024 * 1024
+        } else if suffix.eq_ignore_ascii_case("g") || suffix.eq_ignore_ascii_case("gb") {
+            1000 * 1000 * 1000
+        } else if suffix.eq_ignore_ascii_case("gib") {
+            1024 * 1024 * 1024
+        } else if suffix.eq_ignore_ascii_case("t") || suffix.eq_ignore_ascii_case("tb") {
+            1000 * 1000 * 1000 * 1000
+        } else if suffix.eq_ignore_ascii_case("tib") {
+            1024 * 1024 * 1024 * 1024
+        } else if suffix.eq_ignore_ascii_case("p") || suffix.eq_ignore_ascii_case("pb") {
+            1000 * 1000 * 1000 * 1000 * 1000
+        } else if suffix.eq_ignore_ascii_case("pib") {
+            1024 * 1024 * 1024 * 1024 * 1024
+        } else {
+            return Err(ParseBytesError::UnknownUnit);
+        };
+
+        let mut parts = number.splitn(2, '.');
+        let int_part = parts.next().unwrap_or("");
+        let frac_part = parts.next();
+
+        if int_part.is_empty() && frac_part.map_or(true, str::is_empty) {
+            return Err(ParseBytesError::InvalidNumber);
+        }
+
+        let int_value = if int_part.is_empty() {
+            0
+        } else {
+            parse_digits(int_part)?
+        };
+
+        let mut bytes = int_value
+            .checked_mul(multiplier)
+            .ok_or(ParseBytesError::Overflow)?;
+
+        if let Some(frac_part) = frac_part {
+            if !frac_part.is_empty() {
+                let frac_value = parse_digits(frac_part)?;
+                let scale = 10u64
+                    .checked_pow(frac_part.len() as u32)
+                    .ok_or(ParseBytesError::Overflow)?;
+                let numerator = frac_value
+                    .checked_mul(multiplier)
+                    .ok_or(ParseBytesError::Overflow)?;
+                // Round to the nearest byte.
+                let rounded = numerator
+                    .checked_add(scale / 2)
+                    .ok_or(ParseBytesError::Overflow)?
+                    / scale;
+                bytes = bytes.checked_add(rounded).ok_or(ParseBytesError::Overflow)?;
+            }
+        }
+
+        usize::try_from(bytes)
+            .map(Bytes)
+            .map_err(|_| ParseBytesError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn checked_add_overflow() {
+        assert_eq!(Bytes(1).checked_add(Bytes(2)), Some(Bytes(3)));
+        assert_eq!(Bytes(usize::MAX).checked_add(Bytes(1)), None);
+    }
+
+    #[test]
+    fn checked_sub_underflow() {
+        assert_eq!(Bytes(3).checked_sub(Bytes(2)), Some(Bytes(1)));
+        assert_eq!(Bytes(0).checked_sub(Bytes(1)), None);
+    }
+
+    #[test]
+    fn checked_mul_overflow() {
+        assert_eq!(Bytes(3).checked_mul(Bytes(2)), Some(Bytes(6)));
+        assert_eq!(Bytes(usize::MAX).checked_mul(Bytes(2)), None);
+    }
+
+    #[test]
+    fn saturating_add_clamps_to_max() {
+        assert_eq!(Bytes(1).saturating_add(Bytes(2)), Bytes(3));
+        assert_eq!(
+            Bytes(usize::MAX).saturating_add(Bytes(1)),
+            Bytes(usize::MAX)
+        );
+    }
+
+    #[test]
+    fn saturating_sub_clamps_to_zero() {
+        assert_eq!(Bytes(3).saturating_sub(Bytes(2)), Bytes(1));
+        assert_eq!(Bytes(0).saturating_sub(Bytes(1)), Bytes(0));
+    }
+
+    #[test]
+    fn try_round_up_to_pages_ok() {
+        let pages: Pages = Bytes(PAGE_SIZE.0 + 1).try_round_up_to().unwrap();
+        assert_eq!(pages, Pages(2));
+    }
+
+    #[test]
+    fn try_round_up_to_overflows() {
+        let err: Result<Pages, _> = Bytes(usize::MAX).try_round_up_to_pages(usize::MAX);
+        assert_eq!(err, Err(MemoryError::Overflow));
+    }
+
+    #[test]
+    fn try_round_up_to_pages_rejects_above_max() {
+        let err: Result<Pages, _> = Bytes(PAGE_SIZE.0 + 1).try_round_up_to_pages(1);
+        assert_eq!(
+            err,
+            Err(MemoryError::TooManyPages {
+                pages: 2,
+                max_pages: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn pages_try_to_bytes_overflows() {
+        let max_pages: Pages = Pages(usize::MAX);
+        assert_eq!(max_pages.try_to_bytes(), Err(MemoryError::Overflow));
+
+        let two_pages: Pages = Pages(2);
+        assert_eq!(two_pages.try_to_bytes(), Ok(Bytes(2 * PAGE_SIZE.0)));
+    }
+
+    #[test]
+    fn pages_with_custom_page_size() {
+        let two_pages: Pages<16384> = Pages(2);
+        assert_eq!(two_pages.try_to_bytes(), Ok(Bytes(2 * 16384)));
+
+        let rounded: Pages<16384> = Bytes(16384 + 1).try_round_up_to().unwrap();
+        assert_eq!(rounded, Pages(2));
+    }
+
+    #[test]
+    fn huge_pages_convert_infallibly_to_bytes() {
+        let two_huge_pages: HugePages2Mib = Pages(2);
+        let bytes: Bytes = two_huge_pages.into();
+        assert_eq!(bytes, Bytes(2 * 2 * 1024 * 1024));
+
+        let one_gib_page: HugePages1Gib = Pages(1);
+        let bytes: Bytes = one_gib_page.into();
+        assert_eq!(bytes, Bytes(1024 * 1024 * 1024));
+
+        let rounded: HugePages2Mib = Bytes(2 * 1024 * 1024 + 1).round_up_to();
+        assert_eq!(rounded, Pages(2));
+    }
+
+    #[test]
+    fn huge_pages_convert_between_granularities_via_bytes() {
+        let small_pages: HugePages2Mib = Pages(512);
+        let bytes: Bytes = small_pages.into();
+        let huge_pages: HugePages1Gib = bytes.round_up_to();
+        assert_eq!(huge_pages, Pages(1));
+    }
+
+    #[test]
+    fn human_bytes_omits_decimals_only_for_plain_bytes() {
+        assert_eq!(Bytes(0).to_string_as(true).to_string(), "0 B");
+        assert_eq!(Bytes(1024).to_string_as(true).to_string(), "1.0 KiB");
+    }
+
+    #[test]
+    fn human_bytes_formats_decimal_units() {
+        assert_eq!(Bytes(1_500_000_000).to_string_as(false).to_string(), "1.5 GB");
+        assert_eq!(
+            Bytes(1_500_000_000_000).to_string_as(false).to_string(),
+            "1.5 TB"
+        );
+    }
+
+    #[test]
+    fn bytes_display_defaults_to_binary_units() {
+        assert_eq!(Bytes(0).to_string(), "0 B");
+        assert_eq!(Bytes(1024).to_string(), "1.0 KiB");
+    }
+
+    #[test]
+    fn from_str_parses_binary_and_decimal_suffixes() {
+        assert_eq!("4KiB".parse(), Ok(Bytes(4 * 1024)));
+        assert_eq!("1KB".parse(), Ok(Bytes(1000)));
+        assert_eq!(" 512 B ".parse(), Ok(Bytes(512)));
+        assert_eq!("1.5KiB".parse(), Ok(Bytes(1536)));
+        assert_eq!("2".parse(), Ok(Bytes(2)));
+    }
+
+    #[test]
+    fn from_str_rejects_empty_number() {
+        assert_eq!("KiB".parse::<Bytes>(), Err(ParseBytesError::InvalidNumber));
+        assert_eq!("".parse::<Bytes>(), Err(ParseBytesError::InvalidNumber));
+    }
+
+    #[test]
+    fn from_str_rejects_unknown_unit() {
+        assert_eq!("4XiB".parse::<Bytes>(), Err(ParseBytesError::UnknownUnit));
+    }
+
+    #[test]
+    fn from_str_rejects_overflow() {
+        assert_eq!(
+            "99999999999999999999999999999999999999".parse::<Bytes>(),
+            Err(ParseBytesError::Overflow)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rkyv")]
+    fn rkyv_round_trip_preserves_values_above_4gib() {
+        use rkyv::ser::serializers::BufferSerializer;
+        use rkyv::ser::Serializer;
+        use rkyv::{Deserialize, Infallible};
+
+        let original = Bytes(5_000_000_000);
+
+        let mut serializer = BufferSerializer::new([0u8; 256]);
+        let pos = serializer.serialize_value(&original).unwrap();
+        let buf = serializer.into_inner();
+
+        let archived = unsafe { rkyv::archived_value::<Bytes>(&buf, pos) };
+        let deserialized: Bytes = archived.deserialize(&mut Infallible).unwrap();
+
+        assert_eq!(deserialized, original);
+    }
+}
+